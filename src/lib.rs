@@ -1,4 +1,5 @@
 use std::convert::From;
+use std::fmt;
 use std::io::BufRead;
 use std::io::Lines;
 use std::str::FromStr;
@@ -43,11 +44,27 @@ impl FromStr for BeatmapMode {
 	}
 }
 
+impl fmt::Display for BeatmapMode {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let n = match *self {
+			BeatmapMode::Standard => 0,
+			BeatmapMode::Taiko => 1,
+			BeatmapMode::CatchTheBeat => 2,
+			BeatmapMode::Mania => 3,
+		};
+		write!(f, "{}", n)
+	}
+}
+
+fn write_bool(b: bool) -> u32 {
+	if b { 1 } else { 0 }
+}
+
 #[derive(Debug, Default)]
 pub struct BeatmapGeneral {
 	pub audio_filename: String,
 	pub audio_lead_in: u32,
-	pub preview_time: u32,
+	pub preview_time: i32, // -1 means no preview point (GeneralBuilder's default); the derived Default and a parsed map without PreviewTime give 0
 	pub countdown: bool,
 	pub sample_set: String,
 	pub stack_leniency: f32,
@@ -56,6 +73,20 @@ pub struct BeatmapGeneral {
 	pub widescreen_storyboard: bool,
 }
 
+impl fmt::Display for BeatmapGeneral {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "AudioFilename: {}", self.audio_filename)?;
+		writeln!(f, "AudioLeadIn: {}", self.audio_lead_in)?;
+		writeln!(f, "PreviewTime: {}", self.preview_time)?;
+		writeln!(f, "Countdown: {}", write_bool(self.countdown))?;
+		writeln!(f, "SampleSet: {}", self.sample_set)?;
+		writeln!(f, "StackLeniency: {}", self.stack_leniency)?;
+		writeln!(f, "Mode: {}", self.mode)?;
+		writeln!(f, "LetterboxInBreaks: {}", write_bool(self.letterbox_in_breaks))?;
+		writeln!(f, "WidescreenStoryboard: {}", write_bool(self.widescreen_storyboard))
+	}
+}
+
 #[derive(Debug, Default)]
 pub struct BeatmapMetadata {
 	pub title: String,
@@ -70,6 +101,21 @@ pub struct BeatmapMetadata {
 	pub beatmap_set_id: u64,
 }
 
+impl fmt::Display for BeatmapMetadata {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "Title: {}", self.title)?;
+		writeln!(f, "TitleUnicode: {}", self.title_unicode)?;
+		writeln!(f, "Artist: {}", self.artist)?;
+		writeln!(f, "ArtistUnicode: {}", self.artist_unicode)?;
+		writeln!(f, "Creator: {}", self.creator)?;
+		writeln!(f, "Version: {}", self.version)?;
+		writeln!(f, "Source: {}", self.source)?;
+		writeln!(f, "Tags: {}", self.tags.join(" "))?;
+		writeln!(f, "BeatmapID: {}", self.beatmap_id)?;
+		writeln!(f, "BeatmapSetID: {}", self.beatmap_set_id)
+	}
+}
+
 #[derive(Debug, Default)]
 pub struct BeatmapDifficulty {
 	pub hp_drain_rate: f32,
@@ -80,6 +126,17 @@ pub struct BeatmapDifficulty {
 	pub slider_tick_rate: f32,
 }
 
+impl fmt::Display for BeatmapDifficulty {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "HPDrainRate: {}", self.hp_drain_rate)?;
+		writeln!(f, "CircleSize: {}", self.circle_size)?;
+		writeln!(f, "OverallDifficulty: {}", self.overall_difficulty)?;
+		writeln!(f, "ApproachRate: {}", self.approach_rate)?;
+		writeln!(f, "SliderMultiplier: {}", self.slider_multiplier)?;
+		writeln!(f, "SliderTickRate: {}", self.slider_tick_rate)
+	}
+}
+
 #[derive(Debug)]
 pub enum Event {
 	BackgroundMedia{
@@ -104,6 +161,22 @@ pub enum Event {
 	},
 }
 
+impl fmt::Display for Event {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			Event::BackgroundMedia{ref filepath} => {
+				write!(f, "0,0,\"{}\",0,0", filepath)
+			},
+			Event::Sprite{ref layer, ref origin, ref filepath, x, y} => {
+				write!(f, "Sprite,{},{},\"{}\",{},{}", layer, origin, filepath, x, y)
+			},
+			Event::Animation{ref layer, ref origin, ref filepath, x, y, frame_count, frame_delay, ref loop_type} => {
+				write!(f, "Animation,{},{},\"{}\",{},{},{},{},{}", layer, origin, filepath, x, y, frame_count, frame_delay, loop_type)
+			},
+		}
+	}
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct TimingPoint {
 	pub offset: u32,
@@ -116,16 +189,48 @@ pub struct TimingPoint {
 	pub inherited: bool,
 }
 
-impl TimingPoint {
-	pub fn inherit(&self, prev: &TimingPoint) -> TimingPoint {
-		let mut point = self.clone();
-		if !self.inherited {
-			return point
-		}
+impl fmt::Display for TimingPoint {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{},{},{},{},{},{},{},{}",
+			self.offset,
+			self.milliseconds_per_beat,
+			self.meter,
+			self.sample_type,
+			self.sample_set,
+			self.volume,
+			write_bool(!self.inherited),
+			write_bool(self.kiai_mode))
+	}
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct DifficultyPoint {
+	pub time: u32,
+	pub slider_velocity: f32,
+}
+
+#[derive(Debug, Default)]
+pub struct HitSample {
+	pub normal_set: u32,
+	pub addition_set: u32,
+	pub index: u32,
+	pub volume: u32,
+	pub filename: String,
+}
 
-		point.milliseconds_per_beat = prev.milliseconds_per_beat + self.milliseconds_per_beat;
-		point.inherited = prev.inherited;
-		return point;
+impl HitSample {
+	// True when every field is at its default, as when the source line carried no
+	// extras segment or an all-zero one. Sliders omit the field entirely in that
+	// case to keep the positional edge columns aligned on re-serialization.
+	fn is_default(&self) -> bool {
+		self.normal_set == 0 && self.addition_set == 0 && self.index == 0
+			&& self.volume == 0 && self.filename.is_empty()
+	}
+}
+
+impl fmt::Display for HitSample {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}:{}:{}:{}:{}", self.normal_set, self.addition_set, self.index, self.volume, self.filename)
 	}
 }
 
@@ -135,7 +240,45 @@ pub struct HitObjectBase {
 	pub y: u32, // 0 to 384
 	pub time: u32, // In ms
 	pub object_type: u32, // Bitmap
+	pub new_combo: bool,
+	pub combo_skip: u32, // Number of combo colours to skip, bits 4-6 of the type
 	pub hit_sound: u32,
+	pub hit_sample: HitSample,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SliderKind {
+	Linear,
+	Perfect,
+	#[default]
+	Bezier,
+	Catmull,
+}
+
+impl FromStr for SliderKind {
+	type Err = ();
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"L" => Ok(SliderKind::Linear),
+			"P" => Ok(SliderKind::Perfect),
+			"B" => Ok(SliderKind::Bezier),
+			"C" => Ok(SliderKind::Catmull),
+			_ => Err(()),
+		}
+	}
+}
+
+impl fmt::Display for SliderKind {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let c = match *self {
+			SliderKind::Linear => "L",
+			SliderKind::Perfect => "P",
+			SliderKind::Bezier => "B",
+			SliderKind::Catmull => "C",
+		};
+		write!(f, "{}", c)
+	}
 }
 
 #[derive(Debug)]
@@ -146,12 +289,12 @@ pub enum HitObject {
 	},
 	Slider {
 		base: HitObjectBase,
-		slider_type: u32,
-		//curve
+		slider_type: SliderKind,
+		control_points: Vec<(i32, i32)>,
 		repeat: u32,
-		//pixel_length
-		edge_hitsound: u32,
-		edge_addition: u32,
+		pixel_length: f32,
+		edge_hitsounds: Vec<u32>,
+		edge_additions: Vec<(u32, u32)>,
 	},
 	Spinner {
 		base: HitObjectBase,
@@ -179,6 +322,50 @@ impl HitObject {
 	}
 }
 
+impl fmt::Display for HitObjectBase {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{},{},{},{},{}", self.x, self.y, self.time, self.object_type, self.hit_sound)
+	}
+}
+
+impl fmt::Display for HitObject {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			HitObject::Circle{ref base} => write!(f, "{},{}", base, base.hit_sample),
+			HitObject::Slider{ref base, slider_type, ref control_points, repeat, pixel_length, ref edge_hitsounds, ref edge_additions} => {
+				write!(f, "{},{}", base, slider_type)?;
+				// The first control point is the slider head, already emitted as x,y.
+				for &(x, y) in control_points.iter().skip(1) {
+					write!(f, "|{}:{}", x, y)?;
+				}
+				write!(f, ",{},{}", repeat, pixel_length)?;
+
+				// The trailing sections are positional: field 8 is the edge hitsounds
+				// slot, field 9 the edge additions slot and field 10 the hit sample.
+				// A later field forces every earlier slot to be emitted (empty if it
+				// has no data) so the columns stay aligned and the line re-parses.
+				let need_sample = !base.hit_sample.is_default();
+				if !edge_hitsounds.is_empty() || !edge_additions.is_empty() || need_sample {
+					let sounds: Vec<String> = edge_hitsounds.iter().map(|s| s.to_string()).collect();
+					write!(f, ",{}", sounds.join("|"))?;
+				}
+				if !edge_additions.is_empty() || need_sample {
+					let additions: Vec<String> = edge_additions.iter().map(|&(s, a)| format!("{}:{}", s, a)).collect();
+					write!(f, ",{}", additions.join("|"))?;
+				}
+				if need_sample {
+					write!(f, ",{}", base.hit_sample)?;
+				}
+
+				Ok(())
+			},
+			HitObject::Spinner{ref base, end_time} => write!(f, "{},{},{}", base, end_time, base.hit_sample),
+			HitObject::LongNote{ref base, end_time} => write!(f, "{},{}:{}", base, end_time, base.hit_sample),
+			HitObject::Other(ref base) => write!(f, "{}", base),
+		}
+	}
+}
+
 #[derive(Debug, Default)]
 pub struct Beatmap {
 	pub general: BeatmapGeneral,
@@ -186,44 +373,677 @@ pub struct Beatmap {
 	pub difficulty: BeatmapDifficulty,
 	pub events: Vec<Event>,
 	pub timing_points: Vec<TimingPoint>,
+	pub difficulty_points: Vec<DifficultyPoint>,
 	pub hit_objects: Vec<HitObject>,
 }
 
-fn parse_bool(s: &str) -> Result<bool, &'static str> {
-	match s {
-		"0" => Ok(false),
-		"1" => Ok(true),
-		_ => Err("malformed bool"),
+impl Beatmap {
+	/// Derives the resolved slider-velocity control points from the raw
+	/// `timing_points`, in time order. Inherited points encode their velocity as
+	/// `sv = -100 / milliseconds_per_beat`; uninherited points keep the default.
+	pub fn resolve_difficulty_points(&mut self) {
+		let mut points = self.timing_points.clone();
+		points.sort_by_key(|p| p.offset);
+
+		let mut resolved = Vec::new();
+		for p in &points {
+			if p.inherited {
+				let slider_velocity = if p.milliseconds_per_beat != 0.0 {
+					-100.0 / p.milliseconds_per_beat
+				} else {
+					1.0
+				};
+				resolved.push(DifficultyPoint{time: p.offset, slider_velocity});
+			}
+		}
+
+		self.difficulty_points = resolved;
+	}
+
+	/// Effective BPM at `ms`, from the latest uninherited timing point in effect.
+	///
+	/// Uninherited points in the raw `timing_points` already are the resolved
+	/// BPM/timing list — they carry a positive `milliseconds_per_beat` (beat
+	/// length) directly — so we query them in place rather than duplicating them
+	/// into a second vec. Returns `None` before the first timing point, or when
+	/// the governing point carries a non-positive beat length.
+	pub fn bpm_at(&self, ms: u32) -> Option<f32> {
+		self.timing_points.iter()
+			.filter(|p| !p.inherited && p.offset <= ms)
+			.max_by_key(|p| p.offset)
+			.filter(|p| p.milliseconds_per_beat > 0.0)
+			.map(|p| 60000.0 / p.milliseconds_per_beat)
+	}
+
+	/// Effective slider-velocity multiplier at `ms`, defaulting to `1.0` before
+	/// any inherited point takes effect.
+	pub fn slider_velocity_at(&self, ms: u32) -> f32 {
+		self.difficulty_points.iter()
+			.filter(|p| p.time <= ms)
+			.max_by_key(|p| p.time)
+			.map(|p| p.slider_velocity)
+			.unwrap_or(1.0)
+	}
+}
+
+impl fmt::Display for Beatmap {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "osu file format v14")?;
+		writeln!(f)?;
+
+		writeln!(f, "[General]")?;
+		write!(f, "{}", self.general)?;
+		writeln!(f)?;
+
+		writeln!(f, "[Metadata]")?;
+		write!(f, "{}", self.metadata)?;
+		writeln!(f)?;
+
+		writeln!(f, "[Difficulty]")?;
+		write!(f, "{}", self.difficulty)?;
+		writeln!(f)?;
+
+		writeln!(f, "[Events]")?;
+		for event in &self.events {
+			writeln!(f, "{}", event)?;
+		}
+		writeln!(f)?;
+
+		writeln!(f, "[TimingPoints]")?;
+		for point in &self.timing_points {
+			writeln!(f, "{}", point)?;
+		}
+		writeln!(f)?;
+
+		writeln!(f, "[HitObjects]")?;
+		for object in &self.hit_objects {
+			writeln!(f, "{}", object)?;
+		}
+
+		Ok(())
+	}
+}
+
+pub struct GeneralBuilder {
+	audio_filename: Option<String>,
+	audio_lead_in: u32,
+	preview_time: i32,
+	countdown: bool,
+	sample_set: String,
+	stack_leniency: f32,
+	mode: BeatmapMode,
+	letterbox_in_breaks: bool,
+	widescreen_storyboard: bool,
+}
+
+impl Default for GeneralBuilder {
+	fn default() -> GeneralBuilder {
+		GeneralBuilder{
+			audio_filename: None,
+			audio_lead_in: 0,
+			preview_time: -1,
+			countdown: false,
+			sample_set: "Normal".to_string(),
+			stack_leniency: 0.7,
+			mode: BeatmapMode::Standard,
+			letterbox_in_breaks: false,
+			widescreen_storyboard: false,
+		}
+	}
+}
+
+impl GeneralBuilder {
+	pub fn new() -> GeneralBuilder {
+		GeneralBuilder::default()
+	}
+
+	pub fn audio_filename(mut self, v: &str) -> GeneralBuilder {
+		self.audio_filename = Some(v.to_string());
+		self
+	}
+
+	pub fn audio_lead_in(mut self, v: u32) -> GeneralBuilder {
+		self.audio_lead_in = v;
+		self
+	}
+
+	pub fn preview_time(mut self, v: i32) -> GeneralBuilder {
+		self.preview_time = v;
+		self
+	}
+
+	pub fn countdown(mut self, v: bool) -> GeneralBuilder {
+		self.countdown = v;
+		self
+	}
+
+	pub fn sample_set(mut self, v: &str) -> GeneralBuilder {
+		self.sample_set = v.to_string();
+		self
+	}
+
+	pub fn stack_leniency(mut self, v: f32) -> GeneralBuilder {
+		self.stack_leniency = v;
+		self
+	}
+
+	pub fn mode(mut self, v: BeatmapMode) -> GeneralBuilder {
+		self.mode = v;
+		self
+	}
+
+	pub fn letterbox_in_breaks(mut self, v: bool) -> GeneralBuilder {
+		self.letterbox_in_breaks = v;
+		self
+	}
+
+	pub fn widescreen_storyboard(mut self, v: bool) -> GeneralBuilder {
+		self.widescreen_storyboard = v;
+		self
+	}
+
+	pub fn build(self) -> Result<BeatmapGeneral, ParseError> {
+		let audio_filename = match self.audio_filename {
+			Some(v) => v,
+			None => return Err(ParseError::MissingField("AudioFilename")),
+		};
+
+		Ok(BeatmapGeneral{
+			audio_filename,
+			audio_lead_in: self.audio_lead_in,
+			preview_time: self.preview_time,
+			countdown: self.countdown,
+			sample_set: self.sample_set,
+			stack_leniency: self.stack_leniency,
+			mode: self.mode,
+			letterbox_in_breaks: self.letterbox_in_breaks,
+			widescreen_storyboard: self.widescreen_storyboard,
+		})
+	}
+}
+
+#[derive(Default)]
+pub struct MetadataBuilder {
+	title: String,
+	title_unicode: String,
+	artist: String,
+	artist_unicode: String,
+	creator: String,
+	version: Option<String>,
+	source: String,
+	tags: Vec<String>,
+	beatmap_id: u64,
+	beatmap_set_id: u64,
+}
+
+impl MetadataBuilder {
+	pub fn new() -> MetadataBuilder {
+		MetadataBuilder::default()
+	}
+
+	pub fn title(mut self, v: &str) -> MetadataBuilder {
+		self.title = v.to_string();
+		self
+	}
+
+	pub fn title_unicode(mut self, v: &str) -> MetadataBuilder {
+		self.title_unicode = v.to_string();
+		self
+	}
+
+	pub fn artist(mut self, v: &str) -> MetadataBuilder {
+		self.artist = v.to_string();
+		self
+	}
+
+	pub fn artist_unicode(mut self, v: &str) -> MetadataBuilder {
+		self.artist_unicode = v.to_string();
+		self
+	}
+
+	pub fn creator(mut self, v: &str) -> MetadataBuilder {
+		self.creator = v.to_string();
+		self
+	}
+
+	pub fn version(mut self, v: &str) -> MetadataBuilder {
+		self.version = Some(v.to_string());
+		self
+	}
+
+	pub fn source(mut self, v: &str) -> MetadataBuilder {
+		self.source = v.to_string();
+		self
+	}
+
+	pub fn tags(mut self, v: Vec<String>) -> MetadataBuilder {
+		self.tags = v;
+		self
+	}
+
+	pub fn beatmap_id(mut self, v: u64) -> MetadataBuilder {
+		self.beatmap_id = v;
+		self
+	}
+
+	pub fn beatmap_set_id(mut self, v: u64) -> MetadataBuilder {
+		self.beatmap_set_id = v;
+		self
+	}
+
+	pub fn build(self) -> Result<BeatmapMetadata, ParseError> {
+		let version = match self.version {
+			Some(v) => v,
+			None => return Err(ParseError::MissingField("Version")),
+		};
+
+		Ok(BeatmapMetadata{
+			title: self.title,
+			title_unicode: self.title_unicode,
+			artist: self.artist,
+			artist_unicode: self.artist_unicode,
+			creator: self.creator,
+			version,
+			source: self.source,
+			tags: self.tags,
+			beatmap_id: self.beatmap_id,
+			beatmap_set_id: self.beatmap_set_id,
+		})
+	}
+}
+
+pub struct DifficultyBuilder {
+	hp_drain_rate: f32,
+	circle_size: f32,
+	overall_difficulty: f32,
+	approach_rate: f32,
+	slider_multiplier: f32,
+	slider_tick_rate: f32,
+}
+
+impl Default for DifficultyBuilder {
+	fn default() -> DifficultyBuilder {
+		DifficultyBuilder{
+			hp_drain_rate: 5.0,
+			circle_size: 5.0,
+			overall_difficulty: 5.0,
+			approach_rate: 5.0,
+			slider_multiplier: 1.4,
+			slider_tick_rate: 1.0,
+		}
+	}
+}
+
+impl DifficultyBuilder {
+	pub fn new() -> DifficultyBuilder {
+		DifficultyBuilder::default()
+	}
+
+	pub fn hp_drain_rate(mut self, v: f32) -> DifficultyBuilder {
+		self.hp_drain_rate = v;
+		self
+	}
+
+	pub fn circle_size(mut self, v: f32) -> DifficultyBuilder {
+		self.circle_size = v;
+		self
+	}
+
+	pub fn overall_difficulty(mut self, v: f32) -> DifficultyBuilder {
+		self.overall_difficulty = v;
+		self
+	}
+
+	pub fn approach_rate(mut self, v: f32) -> DifficultyBuilder {
+		self.approach_rate = v;
+		self
+	}
+
+	pub fn slider_multiplier(mut self, v: f32) -> DifficultyBuilder {
+		self.slider_multiplier = v;
+		self
+	}
+
+	pub fn slider_tick_rate(mut self, v: f32) -> DifficultyBuilder {
+		self.slider_tick_rate = v;
+		self
+	}
+
+	pub fn build(self) -> Result<BeatmapDifficulty, ParseError> {
+		Ok(BeatmapDifficulty{
+			hp_drain_rate: self.hp_drain_rate,
+			circle_size: self.circle_size,
+			overall_difficulty: self.overall_difficulty,
+			approach_rate: self.approach_rate,
+			slider_multiplier: self.slider_multiplier,
+			slider_tick_rate: self.slider_tick_rate,
+		})
+	}
+}
+
+pub struct TimingPointBuilder {
+	offset: Option<u32>,
+	milliseconds_per_beat: Option<f32>,
+	meter: u32,
+	sample_type: u32,
+	sample_set: u32,
+	volume: u32,
+	kiai_mode: bool,
+	inherited: bool,
+}
+
+impl Default for TimingPointBuilder {
+	fn default() -> TimingPointBuilder {
+		TimingPointBuilder{
+			offset: None,
+			milliseconds_per_beat: None,
+			meter: 4,
+			sample_type: 0,
+			sample_set: 0,
+			volume: 100,
+			kiai_mode: false,
+			inherited: false,
+		}
+	}
+}
+
+impl TimingPointBuilder {
+	pub fn new() -> TimingPointBuilder {
+		TimingPointBuilder::default()
+	}
+
+	pub fn offset(mut self, v: u32) -> TimingPointBuilder {
+		self.offset = Some(v);
+		self
+	}
+
+	pub fn milliseconds_per_beat(mut self, v: f32) -> TimingPointBuilder {
+		self.milliseconds_per_beat = Some(v);
+		self
+	}
+
+	pub fn meter(mut self, v: u32) -> TimingPointBuilder {
+		self.meter = v;
+		self
+	}
+
+	pub fn sample_type(mut self, v: u32) -> TimingPointBuilder {
+		self.sample_type = v;
+		self
+	}
+
+	pub fn sample_set(mut self, v: u32) -> TimingPointBuilder {
+		self.sample_set = v;
+		self
+	}
+
+	pub fn volume(mut self, v: u32) -> TimingPointBuilder {
+		self.volume = v;
+		self
+	}
+
+	pub fn kiai_mode(mut self, v: bool) -> TimingPointBuilder {
+		self.kiai_mode = v;
+		self
+	}
+
+	pub fn inherited(mut self, v: bool) -> TimingPointBuilder {
+		self.inherited = v;
+		self
+	}
+
+	pub fn build(self) -> Result<TimingPoint, ParseError> {
+		let offset = match self.offset {
+			Some(v) => v,
+			None => return Err(ParseError::MissingField("offset")),
+		};
+		let milliseconds_per_beat = match self.milliseconds_per_beat {
+			Some(v) => v,
+			None => return Err(ParseError::MissingField("milliseconds_per_beat")),
+		};
+
+		Ok(TimingPoint{
+			offset,
+			milliseconds_per_beat,
+			meter: self.meter,
+			sample_type: self.sample_type,
+			sample_set: self.sample_set,
+			volume: self.volume,
+			kiai_mode: self.kiai_mode,
+			inherited: self.inherited,
+		})
+	}
+}
+
+#[derive(Default)]
+pub struct BeatmapBuilder {
+	general: Option<BeatmapGeneral>,
+	metadata: Option<BeatmapMetadata>,
+	difficulty: Option<BeatmapDifficulty>,
+	events: Vec<Event>,
+	timing_points: Vec<TimingPoint>,
+	hit_objects: Vec<HitObject>,
+}
+
+impl BeatmapBuilder {
+	pub fn new() -> BeatmapBuilder {
+		BeatmapBuilder::default()
+	}
+
+	pub fn general(mut self, general: BeatmapGeneral) -> BeatmapBuilder {
+		self.general = Some(general);
+		self
+	}
+
+	pub fn metadata(mut self, metadata: BeatmapMetadata) -> BeatmapBuilder {
+		self.metadata = Some(metadata);
+		self
+	}
+
+	pub fn difficulty(mut self, difficulty: BeatmapDifficulty) -> BeatmapBuilder {
+		self.difficulty = Some(difficulty);
+		self
+	}
+
+	pub fn event(mut self, event: Event) -> BeatmapBuilder {
+		self.events.push(event);
+		self
+	}
+
+	pub fn timing_point(mut self, point: TimingPoint) -> BeatmapBuilder {
+		self.timing_points.push(point);
+		self
+	}
+
+	pub fn hit_object(mut self, object: HitObject) -> BeatmapBuilder {
+		self.hit_objects.push(object);
+		self
+	}
+
+	pub fn build(self) -> Result<Beatmap, ParseError> {
+		let general = match self.general {
+			Some(v) => v,
+			None => return Err(ParseError::MissingField("General")),
+		};
+		let metadata = match self.metadata {
+			Some(v) => v,
+			None => return Err(ParseError::MissingField("Metadata")),
+		};
+
+		let mut beatmap = Beatmap{
+			general,
+			metadata,
+			difficulty: self.difficulty.unwrap_or_default(),
+			events: self.events,
+			timing_points: self.timing_points,
+			difficulty_points: Vec::new(),
+			hit_objects: self.hit_objects,
+		};
+		beatmap.resolve_difficulty_points();
+		Ok(beatmap)
+	}
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+	MissingField(&'static str),
+	InvalidInteger(usize),
+	InvalidFloatingPoint(usize),
+	InvalidFloat(usize),
+	OutOfRange(usize),
+	BadSection(usize),
+	Io(usize),
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			ParseError::MissingField(name) => write!(f, "missing field: {}", name),
+			ParseError::InvalidInteger(line) => write!(f, "invalid integer on line {}", line),
+			ParseError::InvalidFloatingPoint(line) => write!(f, "invalid floating-point number on line {}", line),
+			ParseError::InvalidFloat(line) => write!(f, "non-finite float on line {}", line),
+			ParseError::OutOfRange(line) => write!(f, "value out of range on line {}", line),
+			ParseError::BadSection(line) => write!(f, "malformed section on line {}", line),
+			ParseError::Io(line) => write!(f, "I/O error on line {}", line),
+		}
+	}
+}
+
+impl std::error::Error for ParseError {}
+
+/// A numeric field that can be checked against an inclusive `min..=max` range,
+/// clamping to the nearest bound in lenient mode.
+pub trait InRange: PartialOrd + Sized {
+	fn clamp_range(self, min: Self, max: Self) -> Self;
+}
+
+impl InRange for f32 {
+	fn clamp_range(self, min: f32, max: f32) -> f32 {
+		if self < min {
+			min
+		} else if self > max {
+			max
+		} else {
+			self
+		}
+	}
+}
+
+impl InRange for u32 {
+	fn clamp_range(self, min: u32, max: u32) -> u32 {
+		if self < min {
+			min
+		} else if self > max {
+			max
+		} else {
+			self
+		}
 	}
 }
 
 pub struct Parser<U>  {
 	lines: Lines<U>,
 	section: Option<String>,
+	line: usize,
+	lenient: bool,
 	done: bool,
 }
 
 impl<U> Parser<U> where U: BufRead {
 	pub fn new(lines: Lines<U>) -> Parser<U> {
-		Parser{lines: lines, section: None, done: false}
+		Parser{lines, section: None, line: 0, lenient: false, done: false}
+	}
+
+	/// Clamp out-of-range values instead of erroring on them. Off by default.
+	pub fn lenient(mut self, lenient: bool) -> Parser<U> {
+		self.lenient = lenient;
+		self
+	}
+
+	fn in_range<T: InRange + Copy>(&self, value: T, min: T, max: T) -> Result<T, ParseError> {
+		if value >= min && value <= max {
+			Ok(value)
+		} else if self.lenient {
+			Ok(value.clamp_range(min, max))
+		} else {
+			Err(ParseError::OutOfRange(self.line))
+		}
+	}
+
+	fn parse_bool(&self, v: &str) -> Result<bool, ParseError> {
+		match v {
+			"0" => Ok(false),
+			"1" => Ok(true),
+			_ => Err(ParseError::InvalidInteger(self.line)),
+		}
+	}
+
+	fn parse_u32(&self, v: &str) -> Result<u32, ParseError> {
+		u32::from_str(v).map_err(|_| ParseError::InvalidInteger(self.line))
+	}
+
+	fn parse_u64(&self, v: &str) -> Result<u64, ParseError> {
+		u64::from_str(v).map_err(|_| ParseError::InvalidInteger(self.line))
+	}
+
+	fn parse_i32(&self, v: &str) -> Result<i32, ParseError> {
+		i32::from_str(v).map_err(|_| ParseError::InvalidInteger(self.line))
+	}
+
+	// Parses the trailing `sampleSet:additionSet:customIndex:volume:filename`
+	// segment of a hit object. Missing trailing components keep their defaults.
+	fn parse_hit_sample(&self, v: &str) -> Result<HitSample, ParseError> {
+		let parts: Vec<&str> = v.split(':').collect();
+		let mut sample = HitSample::default();
+		if !parts.is_empty() && !parts[0].is_empty() {
+			sample.normal_set = self.parse_u32(parts[0])?;
+		}
+		if parts.len() > 1 && !parts[1].is_empty() {
+			sample.addition_set = self.parse_u32(parts[1])?;
+		}
+		if parts.len() > 2 && !parts[2].is_empty() {
+			sample.index = self.parse_u32(parts[2])?;
+		}
+		if parts.len() > 3 && !parts[3].is_empty() {
+			sample.volume = self.parse_u32(parts[3])?;
+		}
+		if parts.len() > 4 {
+			sample.filename = parts[4].to_string();
+		}
+		Ok(sample)
 	}
 
-	fn read_header(&mut self) -> Result<String, &'static str> {
+	fn validate_float(&self, n: f32) -> Result<f32, ParseError> {
+		if n.is_finite() {
+			Ok(n)
+		} else {
+			Err(ParseError::InvalidFloat(self.line))
+		}
+	}
+
+	fn parse_f32(&self, v: &str) -> Result<f32, ParseError> {
+		let n = f32::from_str(v).map_err(|_| ParseError::InvalidFloatingPoint(self.line))?;
+		self.validate_float(n)
+	}
+
+	fn read_header(&mut self) -> Result<String, ParseError> {
 		match self.lines.by_ref().next() {
 			Some(Ok(l)) => {
+				self.line += 1;
 				if !l.starts_with("osu file format") {
-					Err("malformed header")
+					Err(ParseError::BadSection(self.line))
 				} else {
 					Ok(l)
 				}
 			},
-			Some(Err(_)) => Err("io error"),
-			None => Err("empty file"),
+			Some(Err(_)) => Err(ParseError::Io(self.line)),
+			None => Err(ParseError::BadSection(self.line)),
 		}
 	}
 
-	fn read_line(&mut self) -> Option<Result<String, &'static str>> {
+	fn read_line(&mut self) -> Option<Result<String, ParseError>> {
 		for line in self.lines.by_ref() {
+			self.line += 1;
 			match line {
 				Ok(l) => {
 					let s = l.trim();
@@ -240,7 +1060,7 @@ impl<U> Parser<U> where U: BufRead {
 					return Some(Ok(s.to_string()));
 				},
 				Err(_) => {
-					return Some(Err("io error"));
+					return Some(Err(ParseError::Io(self.line)));
 				},
 			}
 		}
@@ -249,7 +1069,7 @@ impl<U> Parser<U> where U: BufRead {
 		None
 	}
 
-	fn read_section(&mut self) -> Option<Result<String, &'static str>> {
+	fn read_section(&mut self) -> Option<Result<String, ParseError>> {
 		if self.done {
 			return None;
 		}
@@ -257,24 +1077,24 @@ impl<U> Parser<U> where U: BufRead {
 		if self.section == None {
 			match self.read_line() {
 				None => (),
-				Some(Ok(_)) => return Some(Err("expected a section, not a field")),
+				Some(Ok(_)) => return Some(Err(ParseError::BadSection(self.line))),
 				Some(Err(err)) => return Some(Err(err)),
 			}
 		}
 
 		match self.section.take() {
-			None => Some(Err("expected a section")),
+			None => Some(Err(ParseError::BadSection(self.line))),
 			Some(name) => Some(Ok(name))
 		}
 	}
 
-	fn read_key_value(&mut self) -> Option<Result<(String, String), &'static str>> {
+	fn read_key_value(&mut self) -> Option<Result<(String, String), ParseError>> {
 		if let Some(line) = self.read_line() {
 			match line {
 				Ok(l) => {
 					let kv: Vec<&str> = l.splitn(2, ':').collect();
 					if kv.len() != 2 {
-						return Some(Err("malformed key-value field"));
+						return Some(Err(ParseError::BadSection(self.line)));
 					}
 					Some(Ok((kv[0].trim().to_string(), kv[1].trim().to_string())))
 				},
@@ -285,7 +1105,7 @@ impl<U> Parser<U> where U: BufRead {
 		}
 	}
 
-	fn parse_section(&mut self, name: String, beatmap: &mut Beatmap) -> Result<(), &'static str> {
+	fn parse_section(&mut self, name: String, beatmap: &mut Beatmap) -> Result<(), ParseError> {
 		match name.as_ref() {
 			"General" => self.parse_general(&mut beatmap.general),
 			"Metadata" => self.parse_metadata(&mut beatmap.metadata),
@@ -300,19 +1120,30 @@ impl<U> Parser<U> where U: BufRead {
 		}
 	}
 
-	fn parse_general(&mut self, section: &mut BeatmapGeneral) -> Result<(), &'static str> {
+	fn parse_general(&mut self, section: &mut BeatmapGeneral) -> Result<(), ParseError> {
 		while let Some(res) = self.read_key_value() {
 			let (k, v) = res?;
 			match k.as_ref() {
 				"AudioFilename" => section.audio_filename = v,
-				"AudioLeadIn" => section.audio_lead_in = u32::from_str(&v).unwrap(),
-				"PreviewTime" => section.preview_time = u32::from_str(&v).unwrap(),
-				"Countdown" => section.countdown = parse_bool(&v).unwrap(),
+				"AudioLeadIn" => section.audio_lead_in = self.parse_u32(&v)?,
+				"PreviewTime" => section.preview_time = self.parse_i32(&v)?,
+				"Countdown" => section.countdown = self.parse_bool(&v)?,
 				"SampleSet" => section.sample_set = v,
-				"StackLeniency" => section.stack_leniency = f32::from_str(&v).unwrap(),
-				"Mode" => section.mode = BeatmapMode::from_str(&v).unwrap(),
-				"LetterboxInBreaks" => section.letterbox_in_breaks = parse_bool(&v).unwrap(),
-				"WidescreenStoryboard" => section.widescreen_storyboard = parse_bool(&v).unwrap(),
+				"StackLeniency" => {
+					let n = self.parse_f32(&v)?;
+					section.stack_leniency = self.in_range(n, 0.0, 1.0)?;
+				},
+				"Mode" => {
+					let n = self.in_range(self.parse_u32(&v)?, 0, 3)?;
+					section.mode = match n {
+						1 => BeatmapMode::Taiko,
+						2 => BeatmapMode::CatchTheBeat,
+						3 => BeatmapMode::Mania,
+						_ => BeatmapMode::Standard,
+					};
+				},
+				"LetterboxInBreaks" => section.letterbox_in_breaks = self.parse_bool(&v)?,
+				"WidescreenStoryboard" => section.widescreen_storyboard = self.parse_bool(&v)?,
 				_ => (),
 			}
 		}
@@ -320,7 +1151,7 @@ impl<U> Parser<U> where U: BufRead {
 		Ok(())
 	}
 
-	fn parse_metadata(&mut self, section: &mut BeatmapMetadata) -> Result<(), &'static str> {
+	fn parse_metadata(&mut self, section: &mut BeatmapMetadata) -> Result<(), ParseError> {
 		while let Some(res) = self.read_key_value() {
 			let (k, v) = res?;
 			match k.as_ref() {
@@ -332,8 +1163,8 @@ impl<U> Parser<U> where U: BufRead {
 				"Version" => section.version = v,
 				"Source" => section.source = v,
 				"Tags" => section.tags = v.split(' ').map(|s| s.to_string()).collect(),
-				"BeatmapID" => section.beatmap_id = u64::from_str(&v).unwrap(),
-				"BeatmapSetID" => section.beatmap_set_id = u64::from_str(&v).unwrap(),
+				"BeatmapID" => section.beatmap_id = self.parse_u64(&v)?,
+				"BeatmapSetID" => section.beatmap_set_id = self.parse_u64(&v)?,
 				_ => (),
 			}
 		}
@@ -341,16 +1172,16 @@ impl<U> Parser<U> where U: BufRead {
 		Ok(())
 	}
 
-	fn parse_difficulty(&mut self, section: &mut BeatmapDifficulty) -> Result<(), &'static str> {
+	fn parse_difficulty(&mut self, section: &mut BeatmapDifficulty) -> Result<(), ParseError> {
 		while let Some(res) = self.read_key_value() {
 			let (k, v) = res?;
 			match k.as_ref() {
-				"HPDrainRate" => section.hp_drain_rate = f32::from_str(&v).unwrap(),
-				"CircleSize" => section.circle_size = f32::from_str(&v).unwrap(),
-				"OverallDifficulty" => section.overall_difficulty = f32::from_str(&v).unwrap(),
-				"ApproachRate" => section.approach_rate = f32::from_str(&v).unwrap(),
-				"SliderMultiplier" => section.slider_multiplier = f32::from_str(&v).unwrap(),
-				"SliderTickRate" => section.slider_tick_rate = f32::from_str(&v).unwrap(),
+				"HPDrainRate" => { let n = self.parse_f32(&v)?; section.hp_drain_rate = self.in_range(n, 0.0, 10.0)?; },
+				"CircleSize" => { let n = self.parse_f32(&v)?; section.circle_size = self.in_range(n, 0.0, 10.0)?; },
+				"OverallDifficulty" => { let n = self.parse_f32(&v)?; section.overall_difficulty = self.in_range(n, 0.0, 10.0)?; },
+				"ApproachRate" => { let n = self.parse_f32(&v)?; section.approach_rate = self.in_range(n, 0.0, 10.0)?; },
+				"SliderMultiplier" => section.slider_multiplier = self.parse_f32(&v)?,
+				"SliderTickRate" => section.slider_tick_rate = self.parse_f32(&v)?,
 				_ => (),
 			}
 		}
@@ -358,7 +1189,7 @@ impl<U> Parser<U> where U: BufRead {
 		Ok(())
 	}
 
-	fn parse_events(&mut self, section: &mut Vec<Event>) -> Result<(), &'static str> {
+	fn parse_events(&mut self, section: &mut Vec<Event>) -> Result<(), ParseError> {
 		while let Some(res) = self.read_line() {
 			let l = res?;
 			let values: Vec<&str> = l.split(',').collect();
@@ -376,8 +1207,8 @@ impl<U> Parser<U> where U: BufRead {
 						layer: values[1].to_string(),
 						origin: values[2].to_string(),
 						filepath: values[3].trim_matches('"').to_string(), // TODO: proper unescaping
-						x: u32::from_str(values[4]).unwrap(),
-						y: u32::from_str(values[5]).unwrap(),
+						x: self.parse_u32(values[4])?,
+						y: self.parse_u32(values[5])?,
 					}
 				},
 				"Animation" => {
@@ -389,10 +1220,10 @@ impl<U> Parser<U> where U: BufRead {
 						layer: values[1].to_string(),
 						origin: values[2].to_string(),
 						filepath: values[3].trim_matches('"').to_string(), // TODO: proper unescaping
-						x: u32::from_str(values[4]).unwrap(),
-						y: u32::from_str(values[5]).unwrap(),
-						frame_count: u32::from_str(values[6]).unwrap(),
-						frame_delay: u32::from_str(values[7]).unwrap(),
+						x: self.parse_u32(values[4])?,
+						y: self.parse_u32(values[5])?,
+						frame_count: self.parse_u32(values[6])?,
+						frame_delay: self.parse_u32(values[7])?,
 						loop_type: values[8].to_string(),
 					}
 				},
@@ -413,66 +1244,132 @@ impl<U> Parser<U> where U: BufRead {
 		Ok(())
 	}
 
-	fn parse_timing_points(&mut self, section: &mut Vec<TimingPoint>) -> Result<(), &'static str> {
+	fn parse_timing_points(&mut self, section: &mut Vec<TimingPoint>) -> Result<(), ParseError> {
 		while let Some(res) = self.read_line() {
 			let l = res?;
 			let values: Vec<&str> = l.split(',').collect();
 			if values.len() != 8 {
-				return Err("malformed timing point");
+				return Err(ParseError::BadSection(self.line));
 			}
 
 			section.push(TimingPoint{
-				offset: u32::from_str(values[0]).unwrap(),
-				milliseconds_per_beat: f32::from_str(values[1]).unwrap(),
-				meter: u32::from_str(values[2]).unwrap(),
-				sample_type: u32::from_str(values[3]).unwrap(),
-				sample_set: u32::from_str(values[4]).unwrap(),
-				volume: u32::from_str(values[5]).unwrap(),
-				inherited: !parse_bool(values[6]).unwrap(),
-				kiai_mode: parse_bool(values[7]).unwrap(),
+				offset: self.parse_u32(values[0])?,
+				milliseconds_per_beat: self.parse_f32(values[1])?,
+				meter: self.parse_u32(values[2])?,
+				sample_type: self.parse_u32(values[3])?,
+				sample_set: self.parse_u32(values[4])?,
+				volume: self.in_range(self.parse_u32(values[5])?, 0, 100)?,
+				inherited: !self.parse_bool(values[6])?,
+				kiai_mode: self.parse_bool(values[7])?,
 			});
 		}
 
 		Ok(())
 	}
 
-	fn parse_hit_objects(&mut self, section: &mut Vec<HitObject>) -> Result<(), &'static str> {
+	fn parse_hit_objects(&mut self, section: &mut Vec<HitObject>) -> Result<(), ParseError> {
 		while let Some(res) = self.read_line() {
 			let l = res?;
 			let values: Vec<&str> = l.split(',').collect();
 			if values.len() < 6 {
-				return Err("malformed hit object");
+				return Err(ParseError::BadSection(self.line));
 			}
 
-			let base = HitObjectBase{
-				x: u32::from_str(values[0]).unwrap(),
-				y: u32::from_str(values[1]).unwrap(),
-				time: u32::from_str(values[2]).unwrap(),
-				object_type: u32::from_str(values[3]).unwrap(),
-				hit_sound: u32::from_str(values[4]).unwrap(),
+			let object_type = self.parse_u32(values[3])?;
+			let mut base = HitObjectBase{
+				x: self.parse_u32(values[0])?,
+				y: self.parse_u32(values[1])?,
+				time: self.parse_u32(values[2])?,
+				object_type,
+				new_combo: object_type & 0x04 != 0,
+				combo_skip: (object_type >> 4) & 0x07,
+				hit_sound: self.parse_u32(values[4])?,
+				hit_sample: HitSample::default(),
 			};
 
-			// TODO
 			let object = if base.object_type & 0x01 != 0 {
-				HitObject::Circle{base: base}
+				base.hit_sample = self.parse_hit_sample(values[5])?;
+				HitObject::Circle{base}
 			} else if base.object_type & 0x02 != 0 {
+				if values.len() < 8 {
+					return Err(ParseError::BadSection(self.line));
+				}
+
+				// Field 5 is the curve: `T|x:y|x:y|...`. Keep the slider head as the
+				// first control point so geometry and velocity maths are self-contained.
+				let curve: Vec<&str> = values[5].split('|').collect();
+				let mut slider_type = SliderKind::from_str(curve[0]).map_err(|_| ParseError::BadSection(self.line))?;
+
+				let mut control_points = vec![(base.x as i32, base.y as i32)];
+				for anchor in &curve[1..] {
+					let xy: Vec<&str> = anchor.split(':').collect();
+					if xy.len() != 2 {
+						return Err(ParseError::BadSection(self.line));
+					}
+					control_points.push((self.parse_i32(xy[0])?, self.parse_i32(xy[1])?));
+				}
+
+				// A perfect-circle arc needs exactly three points; anything else is bezier.
+				if slider_type == SliderKind::Perfect && control_points.len() != 3 {
+					slider_type = SliderKind::Bezier;
+				}
+
+				let repeat = self.parse_u32(values[6])?;
+				let pixel_length = self.parse_f32(values[7])?;
+
+				// Older maps omit the edge hitsound/addition sections entirely.
+				let edge_hitsounds = if values.len() > 8 && !values[8].is_empty() {
+					values[8].split('|').map(|s| self.parse_u32(s)).collect::<Result<Vec<u32>, ParseError>>()?
+				} else {
+					Vec::new()
+				};
+				let edge_additions = if values.len() > 9 && !values[9].is_empty() {
+					let mut additions = Vec::new();
+					for pair in values[9].split('|') {
+						let sets: Vec<&str> = pair.split(':').collect();
+						if sets.len() != 2 {
+							return Err(ParseError::BadSection(self.line));
+						}
+						additions.push((self.parse_u32(sets[0])?, self.parse_u32(sets[1])?));
+					}
+					additions
+				} else {
+					Vec::new()
+				};
+
+				if values.len() > 10 {
+					base.hit_sample = self.parse_hit_sample(values[10])?;
+				}
+
 				HitObject::Slider{
-					base: base,
-					slider_type: 0,
-					repeat: 0,
-					edge_hitsound: 0,
-					edge_addition: 0,
+					base,
+					slider_type,
+					control_points,
+					repeat,
+					pixel_length,
+					edge_hitsounds,
+					edge_additions,
 				}
 			} else if base.object_type & 0x08 != 0 {
+				let end_time = self.parse_u32(values[5])?;
+				if values.len() > 6 {
+					base.hit_sample = self.parse_hit_sample(values[6])?;
+				}
 				HitObject::Spinner{
-					base: base,
-					end_time: 0,
+					base,
+					end_time,
 				}
 			} else if base.object_type & 0x80 != 0 {
-				let additional: Vec<&str> = values[5].split(':').collect();
+				// Mania hold notes pack the end time and hit sample into one field:
+				// `endTime:sampleSet:additionSet:index:volume:filename`.
+				let additional: Vec<&str> = values[5].splitn(2, ':').collect();
+				let end_time = self.parse_u32(additional[0])?;
+				if additional.len() > 1 {
+					base.hit_sample = self.parse_hit_sample(additional[1])?;
+				}
 				HitObject::LongNote{
-					base: base,
-					end_time: u32::from_str(additional[0]).unwrap(),
+					base,
+					end_time,
 				}
 			} else {
 				HitObject::Other(base)
@@ -484,7 +1381,7 @@ impl<U> Parser<U> where U: BufRead {
 		Ok(())
 	}
 
-	pub fn parse(&mut self) -> Result<Beatmap, &'static str> {
+	pub fn parse(&mut self) -> Result<Beatmap, ParseError> {
 		let mut beatmap = Beatmap::default();
 
 		self.read_header()?;
@@ -498,6 +1395,81 @@ impl<U> Parser<U> where U: BufRead {
 			}
 		}
 
+		beatmap.resolve_difficulty_points();
 		Ok(beatmap)
 	}
 }
+
+// Async parsing. Enabled with the `async_tokio` or `async_std` cargo features,
+// these read the document off an async reader without blocking the executor and
+// then hand the buffered text to the shared sync `Parser`, so line
+// classification and field parsing never diverge between the two code paths.
+
+#[cfg(feature = "async_tokio")]
+pub struct AsyncParser<R> {
+	reader: R,
+}
+
+#[cfg(feature = "async_tokio")]
+impl<R> AsyncParser<R> where R: tokio::io::AsyncBufRead + Unpin {
+	pub fn new(reader: R) -> AsyncParser<R> {
+		AsyncParser{reader}
+	}
+
+	pub async fn parse(self) -> Result<Beatmap, ParseError> {
+		use tokio::io::AsyncBufReadExt;
+
+		let mut lines = self.reader.lines();
+		let mut buf = String::new();
+		while let Some(line) = lines.next_line().await.map_err(|_| ParseError::Io(0))? {
+			buf.push_str(&line);
+			buf.push('\n');
+		}
+
+		// Disambiguate from tokio's `AsyncBufReadExt::lines` which is in scope here.
+		Parser::new(BufRead::lines(std::io::Cursor::new(buf))).parse()
+	}
+}
+
+#[cfg(feature = "async_tokio")]
+impl Beatmap {
+	pub async fn from_path<P: AsRef<std::path::Path>>(path: P) -> Result<Beatmap, ParseError> {
+		let file = tokio::fs::File::open(path).await.map_err(|_| ParseError::Io(0))?;
+		AsyncParser::new(tokio::io::BufReader::new(file)).parse().await
+	}
+}
+
+#[cfg(all(feature = "async_std", not(feature = "async_tokio")))]
+pub struct AsyncParser<R> {
+	reader: R,
+}
+
+#[cfg(all(feature = "async_std", not(feature = "async_tokio")))]
+impl<R> AsyncParser<R> where R: async_std::io::BufRead + Unpin {
+	pub fn new(reader: R) -> AsyncParser<R> {
+		AsyncParser{reader}
+	}
+
+	pub async fn parse(self) -> Result<Beatmap, ParseError> {
+		use async_std::prelude::*;
+
+		let mut lines = self.reader.lines();
+		let mut buf = String::new();
+		while let Some(line) = lines.next().await {
+			let line = line.map_err(|_| ParseError::Io(0))?;
+			buf.push_str(&line);
+			buf.push('\n');
+		}
+
+		// Disambiguate from the async `lines()` brought in by async_std's prelude.
+		Parser::new(BufRead::lines(std::io::Cursor::new(buf))).parse()
+	}
+}
+
+#[cfg(all(feature = "async_std", not(feature = "async_tokio")))]
+impl Beatmap {
+	pub async fn from_path<P: AsRef<async_std::path::Path>>(path: P) -> Result<Beatmap, ParseError> {
+		let file = async_std::fs::File::open(path).await.map_err(|_| ParseError::Io(0))?;
+		AsyncParser::new(async_std::io::BufReader::new(file)).parse().await
+	}
+}